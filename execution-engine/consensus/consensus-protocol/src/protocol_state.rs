@@ -1,12 +1,148 @@
-use crate::ConsensusContext;
+use std::error;
+use std::fmt;
 use std::hash::Hash;
 
+use crate::ConsensusContext;
+
 pub(crate) trait VertexId {}
 
+/// Relative scheduling priority of a vertex, borrowed from zenoh's
+/// priority-and-reliability model. Higher priority vertices should be
+/// retrievable and processed ahead of lower priority ones, so control-plane
+/// messages aren't stuck behind a backlog of bulk data.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum Priority {
+    Background,
+    Data,
+    Control,
+}
+
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority::Data
+    }
+}
+
+/// Whether a vertex must survive retransmission (retained until
+/// acknowledged) or may be dropped silently under backpressure.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Reliability {
+    Reliable,
+    Unreliable,
+}
+
+/// An inclusive range of priorities a peer is willing to accept.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PriorityRange {
+    min: Priority,
+    max: Priority,
+}
+
+impl PriorityRange {
+    /// `min` and `max` typically come from a peer's wire-advertised range,
+    /// so a malformed advertisement (`min > max`) must be rejected rather
+    /// than crashing the process; hence this is fallible rather than an
+    /// `assert!`.
+    pub(crate) fn new(min: Priority, max: Priority) -> Result<PriorityRange, PriorityRangeError> {
+        if min > max {
+            return Err(PriorityRangeError::Inverted { min, max });
+        }
+
+        Ok(PriorityRange { min, max })
+    }
+
+    /// The widest possible range, i.e. a peer with no narrowing capability.
+    /// `Background..=Control` is known valid at compile time, so this
+    /// bypasses the fallible constructor meant for untrusted input.
+    pub(crate) fn full() -> PriorityRange {
+        PriorityRange {
+            min: Priority::Background,
+            max: Priority::Control,
+        }
+    }
+
+    pub(crate) fn min(&self) -> Priority {
+        self.min
+    }
+
+    pub(crate) fn max(&self) -> Priority {
+        self.max
+    }
+
+    /// The intersection of two peers' supported ranges, so both sides agree
+    /// on a single range that is valid for each of them. Errors when the
+    /// ranges don't overlap at all.
+    pub(crate) fn negotiate(
+        local: PriorityRange,
+        remote: PriorityRange,
+    ) -> Result<PriorityRange, PriorityRangeError> {
+        let min = local.min.max(remote.min);
+        let max = local.max.min(remote.max);
+
+        if min > max {
+            return Err(PriorityRangeError::Disjoint { local, remote });
+        }
+
+        // `min <= max` was just established above, so this can't fail.
+        Ok(PriorityRange { min, max })
+    }
+
+    /// Shifts `priority` to the nearest bound of this range rather than
+    /// rejecting it outright: a priority above `max` is lowered to `max`, one
+    /// below `min` is raised to `min`, so a peer advertising a narrower range
+    /// still gets to participate.
+    pub(crate) fn clamp(&self, priority: Priority) -> Priority {
+        if priority < self.min {
+            self.min
+        } else if priority > self.max {
+            self.max
+        } else {
+            priority
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum PriorityRangeError {
+    /// `min` was greater than `max`, e.g. a malformed peer advertisement.
+    Inverted { min: Priority, max: Priority },
+    /// Two peers advertised priority ranges with no overlap, so no priority
+    /// can be negotiated between them.
+    Disjoint {
+        local: PriorityRange,
+        remote: PriorityRange,
+    },
+}
+
+impl fmt::Display for PriorityRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriorityRangeError::Inverted { min, max } => write!(
+                f,
+                "invalid priority range: min {:?} exceeds max {:?}",
+                min, max
+            ),
+            PriorityRangeError::Disjoint { local, remote } => write!(
+                f,
+                "disjoint priority ranges: local {:?}..={:?}, remote {:?}..={:?}",
+                local.min, local.max, remote.min, remote.max
+            ),
+        }
+    }
+}
+
+impl error::Error for PriorityRangeError {}
+
 pub(crate) trait Vertex<C, Id> {
     fn id(&self) -> Id;
 
     fn values(&self) -> &[C];
+
+    /// The vertex's requested scheduling priority, before any negotiation
+    /// with a peer's supported range.
+    fn priority(&self) -> Priority;
+
+    fn reliability(&self) -> Reliability;
 }
 
 pub(crate) trait ProtocolState<Ctx: ConsensusContext> {
@@ -15,7 +151,89 @@ pub(crate) trait ProtocolState<Ctx: ConsensusContext> {
 
     type Error;
 
-    fn add_vertex(&mut self, v: Self::Vertex) -> Result<Option<Self::VertexId>, Self::Error>;
+    /// The range of vertex priorities this protocol state accepts; used to
+    /// negotiate with a remote peer's own supported range before admitting
+    /// its vertices.
+    fn supported_priority_range(&self) -> PriorityRange;
 
+    /// Admits `v`. `remote_range` is the priority range the sending peer
+    /// advertised support for; `v`'s effective priority is clamped into
+    /// `PriorityRange::negotiate(self.supported_priority_range(), remote_range)`
+    /// rather than the vertex being rejected outright when the peer's range
+    /// is narrower than ours. Unreliable vertices may be dropped under
+    /// backpressure; reliable vertices are retained across retransmission.
+    fn add_vertex(
+        &mut self,
+        v: Self::Vertex,
+        remote_range: PriorityRange,
+    ) -> Result<Option<Self::VertexId>, Self::Error>;
+
+    /// Looks up a vertex by id.
     fn get_vertex(&self, v: Self::VertexId) -> Result<Option<Self::Vertex>, Self::Error>;
+
+    /// Pops the id of the highest-priority pending vertex, if any, so
+    /// control-plane traffic can be scheduled ahead of bulk data instead of
+    /// being processed in arrival order.
+    fn next_pending(&mut self) -> Result<Option<Self::VertexId>, Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_should_outrank_data_and_background() {
+        assert!(Priority::Control > Priority::Data);
+        assert!(Priority::Data > Priority::Background);
+    }
+
+    #[test]
+    fn negotiate_should_return_intersection() {
+        let local =
+            PriorityRange::new(Priority::Background, Priority::Control).expect("valid range");
+        let remote = PriorityRange::new(Priority::Data, Priority::Control).expect("valid range");
+
+        let negotiated = PriorityRange::negotiate(local, remote).expect("should overlap");
+
+        assert_eq!(negotiated.min(), Priority::Data);
+        assert_eq!(negotiated.max(), Priority::Control);
+    }
+
+    #[test]
+    fn negotiate_should_error_on_disjoint_ranges() {
+        let local = PriorityRange::new(Priority::Background, Priority::Data).expect("valid range");
+        let remote =
+            PriorityRange::new(Priority::Control, Priority::Control).expect("valid range");
+
+        assert!(PriorityRange::negotiate(local, remote).is_err());
+    }
+
+    #[test]
+    fn clamp_should_shift_to_nearest_bound() {
+        let range = PriorityRange::new(Priority::Data, Priority::Control).expect("valid range");
+
+        assert_eq!(range.clamp(Priority::Background), Priority::Data);
+        assert_eq!(range.clamp(Priority::Control), Priority::Control);
+        assert_eq!(range.clamp(Priority::Data), Priority::Data);
+    }
+
+    #[test]
+    fn new_should_reject_inverted_range() {
+        let result = PriorityRange::new(Priority::Control, Priority::Background);
+        assert_eq!(
+            result,
+            Err(PriorityRangeError::Inverted {
+                min: Priority::Control,
+                max: Priority::Background,
+            })
+        );
+    }
+
+    #[test]
+    fn full_range_should_not_clamp_anything() {
+        let range = PriorityRange::full();
+
+        assert_eq!(range.clamp(Priority::Background), Priority::Background);
+        assert_eq!(range.clamp(Priority::Control), Priority::Control);
+    }
 }
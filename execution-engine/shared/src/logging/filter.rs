@@ -0,0 +1,171 @@
+///! Per-target runtime log filtering, parsed from an env-style directive
+///! string such as `info,execution_engine::storage=debug,consensus=warning`
+///! (the value one would assign to `RUST_LOG`), following the `log` crate's
+///! target-based filtering model.
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::log_level::{LevelFilter, LogLevel};
+
+/// One parsed directive: either a global default (`target_prefix` is
+/// `None`) or a per-target override.
+#[derive(Clone, Debug, PartialEq)]
+struct Directive {
+    target_prefix: Option<String>,
+    level: LevelFilter,
+}
+
+/// A parsed set of directives controlling, per target, how verbose logging
+/// should be at runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    directives: Vec<Directive>,
+}
+
+impl Filter {
+    /// Whether a record at `level`, emitted from `target`, passes this
+    /// filter. The directive whose `target_prefix` is the longest match
+    /// against `target` wins; if none match, the global default applies.
+    /// Comparison uses `LevelFilter`'s ordering, which -- unlike
+    /// `LogLevel`'s -- is direct rather than inverted: `level` passes when
+    /// it is no more verbose than the configured directive.
+    pub fn enabled(&self, level: LogLevel, target: &str) -> bool {
+        let level: LevelFilter = level.into();
+        level <= self.directive_level_for(target)
+    }
+
+    /// The least restrictive level configured across all directives --
+    /// i.e. the level that would need to be set as the static/runtime max
+    /// level for every directive to have a chance of taking effect.
+    pub fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|directive| directive.level)
+            .max()
+            .unwrap_or(LevelFilter::Debug)
+    }
+
+    fn directive_level_for(&self, target: &str) -> LevelFilter {
+        let mut best_match: Option<&Directive> = None;
+        let mut default: Option<LevelFilter> = None;
+
+        for directive in &self.directives {
+            match &directive.target_prefix {
+                Some(prefix) if target.starts_with(prefix.as_str()) => {
+                    let is_longer = best_match
+                        .and_then(|current| current.target_prefix.as_ref())
+                        .map_or(true, |current_prefix| prefix.len() > current_prefix.len());
+                    if is_longer {
+                        best_match = Some(directive);
+                    }
+                }
+                Some(_) => {}
+                None => default = Some(directive.level),
+            }
+        }
+
+        best_match
+            .map(|directive| directive.level)
+            .or(default)
+            .unwrap_or(LevelFilter::Debug)
+    }
+}
+
+/// A directive string failed to parse, e.g. `execution_engine=verbose`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid log filter directive: {}", self.0)
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl FromStr for Filter {
+    type Err = ParseError;
+
+    fn from_str(spec: &str) -> Result<Filter, ParseError> {
+        let mut directives = Vec::new();
+
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+
+            let (target_prefix, level_str) = match part.find('=') {
+                Some(idx) => (Some(part[..idx].trim().to_string()), &part[idx + 1..]),
+                None => (None, part),
+            };
+
+            let level = parse_level(level_str.trim())?;
+            directives.push(Directive {
+                target_prefix,
+                level,
+            });
+        }
+
+        Ok(Filter { directives })
+    }
+}
+
+fn parse_level(level_str: &str) -> Result<LevelFilter, ParseError> {
+    match level_str.to_ascii_lowercase().as_str() {
+        "off" => Ok(LevelFilter::Off),
+        "fatal" => Ok(LevelFilter::Fatal),
+        "error" => Ok(LevelFilter::Error),
+        "warning" | "warn" => Ok(LevelFilter::Warning),
+        "info" => Ok(LevelFilter::Info),
+        "debug" => Ok(LevelFilter::Debug),
+        other => Err(ParseError(other.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_global_default_only() {
+        let filter: Filter = "info".parse().expect("should parse");
+        assert!(filter.enabled(LogLevel::Info, "anything"));
+        assert!(!filter.enabled(LogLevel::Debug, "anything"));
+    }
+
+    #[test]
+    fn should_apply_longest_matching_target_prefix() {
+        let filter: Filter = "info,execution_engine::storage=debug,consensus=warning"
+            .parse()
+            .expect("should parse");
+
+        assert!(filter.enabled(LogLevel::Debug, "execution_engine::storage::trie"));
+        assert!(!filter.enabled(LogLevel::Debug, "execution_engine::other"));
+        assert!(!filter.enabled(LogLevel::Info, "consensus::highway"));
+        assert!(filter.enabled(LogLevel::Warning, "consensus::highway"));
+        assert!(filter.enabled(LogLevel::Info, "unrelated::module"));
+    }
+
+    #[test]
+    fn should_default_to_debug_when_nothing_configured() {
+        let filter: Filter = "".parse().expect("empty spec should parse");
+        assert!(filter.enabled(LogLevel::Debug, "anything"));
+    }
+
+    #[test]
+    fn should_reject_unrecognized_level() {
+        let result: Result<Filter, _> = "execution_engine=verbose".parse();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn max_level_should_be_least_restrictive_directive() {
+        let filter: Filter = "warning,consensus=debug"
+            .parse()
+            .expect("should parse");
+
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+}
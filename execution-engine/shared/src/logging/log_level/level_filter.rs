@@ -0,0 +1,243 @@
+///! `LevelFilter` mirrors `LogLevel` but adds an `Off` sentinel that is only
+///! ever meaningful as a filtering threshold; runtime logging itself still
+///! disallows "none" as an actual log level.
+///!
+///! Filtering happens in two stages, borrowed from the `log` crate:
+///!   1. a compile-time `STATIC_MAX_LEVEL`, selected via cargo features, which
+///!      lets disabled log statements be optimized out entirely
+///!   2. a process-global, runtime-adjustable max level
+///!
+///! Both stages compare against `LevelFilter`'s ordering. Unlike `LogLevel`,
+///! whose `Ord` impl inverts the numeric discriminants to match semantic
+///! severity, `LevelFilter`'s discriminants are already assigned in
+///! increasing-verbosity order (`Off` lowest, `Debug` highest), so its `Ord`
+///! impl compares them directly, with no inversion.
+use std::cmp::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+use super::LogLevel;
+
+/// A filtering threshold for log levels; adds `Off` to `LogLevel`'s five
+/// variants so "log nothing" can be expressed without making `Off` a valid
+/// level to log *at*.
+#[repr(usize)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum LevelFilter {
+    /// disables all logging
+    Off = 0,
+    /// emergency, alert, critical
+    Fatal = 1,
+    /// error
+    Error = 2,
+    /// warnings
+    Warning = 3,
+    /// notice, informational
+    Info = 4,
+    /// debug, dev oriented messages
+    Debug = 5,
+}
+
+impl LevelFilter {
+    fn as_usize(self) -> usize {
+        self as usize
+    }
+}
+
+impl Ord for LevelFilter {
+    /// discriminants already increase with verbosity (`Off` lowest, `Debug`
+    /// highest), so this is a direct comparison -- no inversion needed,
+    /// unlike `LogLevel::cmp`
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_usize().cmp(&other.as_usize())
+    }
+}
+
+impl PartialOrd for LevelFilter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(log_level: LogLevel) -> LevelFilter {
+        match log_level {
+            LogLevel::Fatal => LevelFilter::Fatal,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warning => LevelFilter::Warning,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+        }
+    }
+}
+
+impl std::convert::TryFrom<LevelFilter> for LogLevel {
+    type Error = ();
+
+    /// `Off` has no `LogLevel` counterpart, since "no log" is not an option
+    /// for an actual log event
+    fn try_from(filter: LevelFilter) -> Result<LogLevel, ()> {
+        match filter {
+            LevelFilter::Off => Err(()),
+            LevelFilter::Fatal => Ok(LogLevel::Fatal),
+            LevelFilter::Error => Ok(LogLevel::Error),
+            LevelFilter::Warning => Ok(LogLevel::Warning),
+            LevelFilter::Info => Ok(LogLevel::Info),
+            LevelFilter::Debug => Ok(LogLevel::Debug),
+        }
+    }
+}
+
+/// The compile-time ceiling on log verbosity, selected via cargo features.
+/// Log statements above this level are not merely skipped, they compile to
+/// nothing at all, so disabled debug logging carries zero runtime cost.
+///
+/// Feature precedence (most restrictive wins if more than one is set):
+/// `max_level_off`, `max_level_fatal`, `max_level_error`, `max_level_warning`,
+/// `max_level_info`, `max_level_debug`; the `release_max_level_*` variants
+/// take effect instead when `debug_assertions` is disabled.
+#[cfg(not(debug_assertions))]
+pub const STATIC_MAX_LEVEL: LevelFilter = const_max_level_release();
+
+#[cfg(debug_assertions)]
+pub const STATIC_MAX_LEVEL: LevelFilter = const_max_level_debug();
+
+#[cfg(not(debug_assertions))]
+const fn const_max_level_release() -> LevelFilter {
+    if cfg!(feature = "release_max_level_off") {
+        LevelFilter::Off
+    } else if cfg!(feature = "release_max_level_fatal") {
+        LevelFilter::Fatal
+    } else if cfg!(feature = "release_max_level_error") {
+        LevelFilter::Error
+    } else if cfg!(feature = "release_max_level_warning") {
+        LevelFilter::Warning
+    } else if cfg!(feature = "release_max_level_info") {
+        LevelFilter::Info
+    } else if cfg!(feature = "release_max_level_debug") {
+        LevelFilter::Debug
+    } else {
+        const_max_level_debug()
+    }
+}
+
+const fn const_max_level_debug() -> LevelFilter {
+    if cfg!(feature = "max_level_off") {
+        LevelFilter::Off
+    } else if cfg!(feature = "max_level_fatal") {
+        LevelFilter::Fatal
+    } else if cfg!(feature = "max_level_error") {
+        LevelFilter::Error
+    } else if cfg!(feature = "max_level_warning") {
+        LevelFilter::Warning
+    } else if cfg!(feature = "max_level_info") {
+        LevelFilter::Info
+    } else if cfg!(feature = "max_level_debug") {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Debug
+    }
+}
+
+/// process-global runtime max level; stored as a `usize` so it can live in
+/// an `AtomicUsize`, interpreted via `LevelFilter`'s `repr(usize)`
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Debug as usize);
+
+/// Adjusts the runtime log level ceiling. Has no effect on log statements
+/// already compiled out by `STATIC_MAX_LEVEL`.
+pub fn set_max_level(filter: LevelFilter) {
+    MAX_LEVEL.store(filter.as_usize(), AtomicOrdering::SeqCst);
+}
+
+/// The current runtime log level ceiling.
+pub fn max_level() -> LevelFilter {
+    match MAX_LEVEL.load(AtomicOrdering::SeqCst) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Fatal,
+        2 => LevelFilter::Error,
+        3 => LevelFilter::Warning,
+        4 => LevelFilter::Info,
+        _ => LevelFilter::Debug,
+    }
+}
+
+/// Serializes test access to `MAX_LEVEL`. `MAX_LEVEL` is a single
+/// process-global, and `cargo test` runs tests for this crate concurrently
+/// by default, so any test that sets or relies on a particular runtime
+/// level must hold this lock for the duration of its assertions -- both
+/// the ones that call `set_max_level` and the ones that assume the
+/// untouched default, since either can otherwise observe another test's
+/// transient mutation.
+#[cfg(test)]
+pub(crate) static TEST_GUARD: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Checks whether a given level passes both the compile-time and runtime
+/// filters. The compile-time check is evaluated first and is a `const`
+/// comparison, so when `level` is statically disabled the runtime check
+/// (and anything it would have gated) is never reached.
+#[macro_export]
+macro_rules! log_enabled {
+    ($level:expr) => {{
+        let level: $crate::logging::log_level::LevelFilter = $level.into();
+        level <= $crate::logging::log_level::STATIC_MAX_LEVEL
+            && level <= $crate::logging::log_level::max_level()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn off_should_be_least_verbose() {
+        assert!(LevelFilter::Off < LevelFilter::Fatal, "off should be least verbose");
+    }
+
+    #[test]
+    fn debug_should_be_most_verbose() {
+        assert!(
+            LevelFilter::Debug > LevelFilter::Info,
+            "debug should be most verbose"
+        );
+    }
+
+    #[test]
+    fn log_level_should_convert_to_level_filter() {
+        let filter: LevelFilter = LogLevel::Warning.into();
+        assert_eq!(filter, LevelFilter::Warning);
+    }
+
+    #[test]
+    fn level_filter_should_convert_to_log_level() {
+        let level = LogLevel::try_from(LevelFilter::Error).expect("should convert");
+        assert_eq!(level, LogLevel::Error);
+    }
+
+    #[test]
+    fn off_should_not_convert_to_log_level() {
+        assert!(LogLevel::try_from(LevelFilter::Off).is_err());
+    }
+
+    #[test]
+    fn default_max_level_should_be_debug() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn set_max_level_should_change_runtime_level() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+        set_max_level(LevelFilter::Warning);
+        assert_eq!(max_level(), LevelFilter::Warning);
+        set_max_level(LevelFilter::Debug);
+    }
+
+    #[test]
+    fn log_enabled_should_respect_runtime_level() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+        set_max_level(LevelFilter::Error);
+        assert!(log_enabled!(LogLevel::Fatal));
+        assert!(!log_enabled!(LogLevel::Info));
+        set_max_level(LevelFilter::Debug);
+    }
+}
@@ -16,6 +16,12 @@ use std::fmt;
 
 use serde::Serialize;
 
+mod level_filter;
+
+pub use level_filter::{max_level, set_max_level, LevelFilter, STATIC_MAX_LEVEL};
+#[cfg(test)]
+pub(crate) use level_filter::TEST_GUARD;
+
 /// LogLevels to be used in CasperLabs EE logic
 #[repr(u8)] // https://doc.rust-lang.org/1.6.0/nomicon/other-reprs.html
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize)]
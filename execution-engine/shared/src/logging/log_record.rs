@@ -0,0 +1,208 @@
+///! Structured, key-value log records.
+///!
+///! `LogRecord` carries a message plus an arbitrary bag of structured fields,
+///! in the spirit of `slog`. `Logger` mirrors rust-lightning's logger trait:
+///! it is a single `log` method so each subsystem can hold its own logger
+///! (file, syslog, stdout, a test sink, ...) behind a trait object.
+use std::fmt;
+
+use serde_json::Value;
+
+use super::log_level::{log_enabled, LogLevel};
+
+/// A single structured log event.
+///
+/// Fields are kept in an order-preserving `Vec` rather than a sorted map, so
+/// the order fields were attached in round-trips into `to_json()`: two
+/// records built the same way, in the same order, always serialize to the
+/// same line, which keeps logs diffable across runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    level: LogLevel,
+    target: String,
+    message: String,
+    fields: Vec<(String, Value)>,
+}
+
+impl LogRecord {
+    /// Builds a new record, or `None` if `level` is filtered out by the
+    /// compile-time or runtime max level. Checking here -- before any field
+    /// is attached or any serialization happens -- is what makes a disabled
+    /// log statement cheap: nothing past this point runs.
+    pub fn new<T: Into<String>, M: Into<String>>(
+        level: LogLevel,
+        target: T,
+        message: M,
+    ) -> Option<LogRecord> {
+        if !log_enabled!(level) {
+            return None;
+        }
+
+        Some(LogRecord {
+            level,
+            target: target.into(),
+            message: message.into(),
+            fields: Vec::new(),
+        })
+    }
+
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Structured fields, in the order they were attached.
+    pub fn fields(&self) -> &[(String, Value)] {
+        &self.fields
+    }
+
+    /// Attaches a structured field. If `key` was already attached, its
+    /// value is updated in place rather than the field being appended
+    /// again, so insertion order still reflects first use of the key.
+    pub fn with_field<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> LogRecord {
+        let key = key.into();
+        let value = value.into();
+
+        match self.fields.iter_mut().find(|(k, _)| *k == key) {
+            Some(existing) => existing.1 = value,
+            None => self.fields.push((key, value)),
+        }
+
+        self
+    }
+
+    /// Emits the record as a single machine-parseable JSON line, with
+    /// `fields` in attachment order. Built by hand rather than via
+    /// `serde_json::Map` because `Map`'s default backing store sorts by
+    /// key, which would silently discard that ordering.
+    pub fn to_json(&self) -> String {
+        let fields: String = self
+            .fields
+            .iter()
+            .map(|(key, value)| format!("{}:{}", json_string(key), value))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        format!(
+            "{{\"level\":{},\"target\":{},\"message\":{},\"fields\":{{{}}}}}",
+            serde_json::to_string(&self.level).unwrap_or_else(|_| "null".to_string()),
+            json_string(&self.target),
+            json_string(&self.message),
+            fields,
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+/// Implemented by each subsystem's log sink (file, syslog, stdout, a test
+/// collector, ...). A `LogRecord` has already passed the level filter by
+/// the time it reaches `log`, so implementors do not need to re-check it.
+pub trait Logger {
+    fn log(&self, record: &LogRecord);
+}
+
+/// Builds a `LogRecord` with `target` defaulting to the caller's module
+/// path, mirroring how the `log` crate's macros default their target.
+#[macro_export]
+macro_rules! log_record {
+    ($level:expr, $message:expr) => {
+        $crate::logging::log_record::LogRecord::new($level, module_path!(), $message)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::log_level::TEST_GUARD;
+
+    #[test]
+    fn should_preserve_field_attachment_order() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+
+        let record = LogRecord::new(LogLevel::Info, "test::target", "hello")
+            .expect("info should be enabled by default")
+            .with_field("b", 2)
+            .with_field("a", 1);
+
+        let keys: Vec<&String> = record.fields().iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec!["b", "a"], "fields should keep attachment order");
+    }
+
+    #[test]
+    fn with_field_should_update_existing_key_in_place() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+
+        let record = LogRecord::new(LogLevel::Info, "test::target", "hello")
+            .expect("info should be enabled by default")
+            .with_field("a", 1)
+            .with_field("b", 2)
+            .with_field("a", 3);
+
+        let fields: Vec<(&String, &Value)> =
+            record.fields().iter().map(|(k, v)| (k, v)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                (&"a".to_string(), &Value::from(3)),
+                (&"b".to_string(), &Value::from(2)),
+            ],
+            "re-attaching a key should update in place, not move to the end"
+        );
+    }
+
+    #[test]
+    fn to_json_should_include_message_and_fields() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+
+        let record = LogRecord::new(LogLevel::Warning, "test::target", "uh oh")
+            .expect("warning should be enabled by default")
+            .with_field("count", 3);
+
+        let json = record.to_json();
+        assert!(json.contains("\"message\":\"uh oh\""));
+        assert!(json.contains("\"count\":3"));
+    }
+
+    #[test]
+    fn to_json_should_preserve_field_order() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+
+        let record = LogRecord::new(LogLevel::Info, "test::target", "hello")
+            .expect("info should be enabled by default")
+            .with_field("z", 1)
+            .with_field("a", 2);
+
+        let json = record.to_json();
+        let z_pos = json.find("\"z\"").expect("z field present");
+        let a_pos = json.find("\"a\"").expect("a field present");
+        assert!(z_pos < a_pos, "fields should serialize in attachment order");
+    }
+
+    #[test]
+    fn new_should_return_none_when_filtered_out() {
+        use crate::logging::log_level::{set_max_level, LevelFilter};
+
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|e| e.into_inner());
+
+        set_max_level(LevelFilter::Error);
+        let record = LogRecord::new(LogLevel::Debug, "test::target", "noisy");
+        assert!(record.is_none(), "debug should be filtered out");
+        set_max_level(LevelFilter::Debug);
+    }
+}
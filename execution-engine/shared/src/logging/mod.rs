@@ -0,0 +1,4 @@
+pub mod filter;
+pub mod log_level;
+pub mod log_record;
+pub mod syslog_formatter;